@@ -0,0 +1,162 @@
+// JWT bearer authentication.
+//
+// Users are stored with an argon2 password hash and a set of roles; login
+// issues a signed JWT carrying the username as `sub` and the roles, and
+// `AuthenticatedUser` is an extractor that validates the
+// `Authorization: Bearer` header the same way handlers already pull out
+// `web::Path`/`web::Json` parameters. The only role with special meaning
+// today is `"admin"`, which bypasses per-model ownership checks.
+
+use actix_web::dev::Payload;
+use actix_web::http::header::AUTHORIZATION;
+use actix_web::{web, FromRequest, HttpRequest};
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::{ready, Ready};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use utoipa::ToSchema;
+
+/// How long an issued JWT stays valid for, in seconds.
+const TOKEN_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// A registered user: an argon2 password hash and the roles encoded into
+/// their JWT.
+pub struct User {
+    pub password_hash: String,
+    pub roles: Vec<String>,
+}
+
+/// Thread-safe store of registered users and the secret used to sign and
+/// verify JWTs, held alongside the rest of `AppState`.
+pub struct AuthState {
+    pub users: Mutex<HashMap<String, User>>,
+    pub jwt_secret: String,
+}
+
+impl AuthState {
+    pub fn new(jwt_secret: impl Into<String>) -> Self {
+        Self {
+            users: Mutex::new(HashMap::new()),
+            jwt_secret: jwt_secret.into(),
+        }
+    }
+}
+
+/// Request body shared by `/api/auth/register` and `/api/auth/login`.
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// The claims encoded into an issued JWT.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub roles: Vec<String>,
+    pub exp: i64,
+}
+
+impl Claims {
+    pub fn is_admin(&self) -> bool {
+        self.roles.iter().any(|role| role == "admin")
+    }
+}
+
+/// Is `user` allowed to create/update/delete data for a model owned by
+/// `owner`? Admins can mutate any model; everyone else only their own.
+/// A model with no recorded owner (e.g. uploaded before this schema
+/// existed) is left open to any authenticated user.
+pub fn can_mutate(owner: Option<&str>, user: &Claims) -> bool {
+    user.is_admin() || owner.map_or(true, |name| name == user.sub)
+}
+
+/// Register a new user, hashing their password with argon2. Fails if the
+/// username is already taken.
+pub fn register(users: &mut HashMap<String, User>, creds: &Credentials) -> Result<(), String> {
+    if users.contains_key(&creds.username) {
+        return Err(format!("user '{}' already exists", creds.username));
+    }
+
+    let salt = SaltString::generate(&mut OsRng);
+    let password_hash = Argon2::default()
+        .hash_password(creds.password.as_bytes(), &salt)
+        .map_err(|e| e.to_string())?
+        .to_string();
+
+    users.insert(
+        creds.username.clone(),
+        User { password_hash, roles: vec!["user".to_string()] },
+    );
+
+    Ok(())
+}
+
+/// Check a plaintext password against a user's stored argon2 hash.
+pub fn verify_password(user: &User, password: &str) -> bool {
+    match PasswordHash::new(&user.password_hash) {
+        Ok(hash) => Argon2::default().verify_password(password.as_bytes(), &hash).is_ok(),
+        Err(_) => false,
+    }
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
+}
+
+/// Sign a JWT for `username` carrying their current `roles`.
+pub fn issue_token(secret: &str, username: &str, roles: &[String]) -> Result<String, String> {
+    let claims = Claims {
+        sub: username.to_string(),
+        roles: roles.to_vec(),
+        exp: now_unix() + TOKEN_TTL_SECS,
+    };
+
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes()))
+        .map_err(|e| e.to_string())
+}
+
+fn decode_token(secret: &str, token: &str) -> Result<Claims, String> {
+    decode::<Claims>(token, &DecodingKey::from_secret(secret.as_bytes()), &Validation::default())
+        .map(|data| data.claims)
+        .map_err(|e| e.to_string())
+}
+
+/// Extracts and validates the `Authorization: Bearer <jwt>` header,
+/// rejecting the request with 401 if it's missing, malformed, or expired.
+/// Handlers that need to know who's calling take this as a parameter.
+pub struct AuthenticatedUser(pub Claims);
+
+impl FromRequest for AuthenticatedUser {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(extract(req))
+    }
+}
+
+fn extract(req: &HttpRequest) -> Result<AuthenticatedUser, actix_web::Error> {
+    let auth_state = req
+        .app_data::<web::Data<AuthState>>()
+        .ok_or_else(|| actix_web::error::ErrorInternalServerError("auth state missing"))?;
+
+    let header = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| actix_web::error::ErrorUnauthorized("missing bearer token"))?;
+
+    let token = header
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| actix_web::error::ErrorUnauthorized("expected a bearer token"))?;
+
+    decode_token(&auth_state.jwt_secret, token)
+        .map(AuthenticatedUser)
+        .map_err(|_| actix_web::error::ErrorUnauthorized("invalid or expired token"))
+}
@@ -0,0 +1,81 @@
+// Content-addressable storage for binary fields.
+//
+// Schema properties marked `format: "binary"` (or the custom `x-blob`
+// keyword) are uploaded as multipart file parts instead of inline JSON.
+// Each upload is addressed by the SHA-256 hash of its bytes (displayed as
+// base58), deduplicating identical uploads, with only that hash persisted
+// inside the item's JSON. `GET /api/blob/{hash}` serves the bytes back.
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// A directory of content-addressed blobs: `<dir>/<hash>` holds the raw
+/// bytes and `<dir>/<hash>.mime` holds the optional MIME type that was
+/// reported when it was uploaded.
+pub struct BlobStore {
+    dir: PathBuf,
+}
+
+impl BlobStore {
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Store `bytes`, deduplicating by content hash, and return the
+    /// base58-encoded SHA-256 hash addressing it.
+    pub fn put(&self, bytes: &[u8], mime: Option<&str>) -> io::Result<String> {
+        let hash = bs58::encode(Sha256::digest(bytes)).into_string();
+        let path = self.dir.join(&hash);
+
+        if !path.exists() {
+            fs::write(&path, bytes)?;
+        }
+
+        if let Some(mime) = mime {
+            fs::write(self.mime_path(&hash), mime)?;
+        }
+
+        Ok(hash)
+    }
+
+    /// Fetch the bytes and recorded MIME type (if any) for `hash`.
+    pub fn get(&self, hash: &str) -> io::Result<Option<(Vec<u8>, Option<String>)>> {
+        let path = self.dir.join(hash);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let bytes = fs::read(path)?;
+        let mime = fs::read_to_string(self.mime_path(hash)).ok();
+
+        Ok(Some((bytes, mime)))
+    }
+
+    fn mime_path(&self, hash: &str) -> PathBuf {
+        self.dir.join(format!("{hash}.mime"))
+    }
+}
+
+/// Property names in a JSON Schema object marked for blob storage via
+/// `format: "binary"` or the custom `x-blob` keyword.
+pub fn blob_fields(schema: &Value) -> Vec<String> {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| {
+            properties
+                .iter()
+                .filter(|(_, prop)| {
+                    prop.get("format").and_then(Value::as_str) == Some("binary")
+                        || prop.get("x-blob").and_then(Value::as_bool) == Some(true)
+                })
+                .map(|(name, _)| name.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -3,20 +3,71 @@
 // approach with `serde_json::Value` and validates data at runtime.
 //
 // This version uses `actix-files` to serve static Swagger UI assets and
-// a separate route to serve the OpenAPI specification.
+// a separate route to serve the OpenAPI specification. The OpenAPI
+// document itself is no longer frozen at compile time: `upload_schema`
+// and `delete_schema` translate the uploaded JSON Schema into `utoipa`
+// schema/path definitions and merge them into `AppState::apidocs`, so
+// Swagger UI always reflects whichever models are currently uploaded.
+//
+// Writes are gated behind JWT bearer auth (see the `auth` module): callers
+// register/login under `/api/auth`, and mutating handlers require a valid
+// token whose subject owns the target model (or carries the `admin` role).
+//
+// Item data itself is no longer a bare in-memory map: `storage::Store`
+// appends every create/update/delete as an immutable edit to a log file
+// and derives current state by replaying it, so a restart doesn't lose
+// data and `/api/{model_name}/{id}/history` and `/api/changelog` have
+// something to report.
+//
+// Schema properties marked `format: "binary"` (or `x-blob`) accept their
+// value as an uploaded file instead of inline JSON: POSTing/PUTing such a
+// model as `multipart/form-data` routes to `blobs`, which stores each part
+// content-addressed by its SHA-256 hash and keeps only that hash in the
+// item's JSON. `GET /api/blob/{hash}` serves the bytes back.
+//
+// `upload_schema` compiles the JSON Schema once into a `schema_cache::
+// CompiledSchema` and caches it (rejecting malformed schemas with a 400
+// right away), so `create_item`/`update_item` validate against the cached
+// validator instead of recompiling the schema on every request.
+//
+// `POST /api/{model_name}/batch` seeds many records in one call: every
+// element is validated up front, and the batch is rejected with per-index
+// errors (inserting nothing) if any of them fail.
+
+// JWT bearer authentication: users, password hashing, and the
+// `AuthenticatedUser` extractor.
+mod auth;
+// Content-addressable storage for binary (multipart-uploaded) fields.
+mod blobs;
+// The filter expression grammar used by the list/query endpoint.
+mod query;
+// Compiled-and-cached JSON Schema validators.
+mod schema_cache;
+// Append-only, log-backed storage for item data.
+mod storage;
 
 // Import necessary crates.
-use actix_web::{http, web, App, HttpResponse, HttpServer, Responder};
+use actix_web::{guard, http, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
 use actix_cors::Cors;
+use actix_multipart::Multipart;
+use bytes::BytesMut;
+use futures_util::TryStreamExt;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use jsonschema::JSONSchema;
 use std::collections::HashMap;
-use std::sync::{Mutex};
+use std::sync::{Arc, Mutex};
 use std::io;
 
 // Import utoipa and utoipa-swagger-ui-dist.
 use utoipa::{OpenApi, ToSchema};
+use utoipa::openapi::{self, Components};
+use utoipa::openapi::path::{OperationBuilder, Parameter, ParameterIn, PathItem, PathItemType};
+use utoipa::openapi::request_body::RequestBodyBuilder;
+use utoipa::openapi::response::ResponseBuilder;
+use utoipa::openapi::{ContentBuilder, Required};
+use utoipa::openapi::schema::{ArrayBuilder, ObjectBuilder, Ref, RefOr, Schema, SchemaType};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme};
+use utoipa::Modify;
 
 // Add the 'actix-files' crate for serving static files.
 use actix_files::Files;
@@ -24,20 +75,43 @@ use actix_files::Files;
 // Define a constant for the default port number.
 const DEFAULT_PORT: u16 = 7777;
 
+// Fallback JWT signing secret, used only when the `JWT_SECRET` environment
+// variable isn't set. Fine for local development; set `JWT_SECRET` for
+// anything that matters.
+const DEFAULT_JWT_SECRET: &str = "dev-secret-change-me";
+
+// Default location of the append-only edit log backing `storage::Store`,
+// used unless the `DYNAMIC_API_LOG_PATH` environment variable overrides it.
+const DEFAULT_LOG_PATH: &str = "data/changelog.log";
+
+// Default directory for content-addressed blobs backing `blobs::BlobStore`,
+// used unless the `DYNAMIC_API_BLOB_DIR` environment variable overrides it.
+const DEFAULT_BLOB_DIR: &str = "data/blobs";
+
 // Define the API documentation using the `OpenApi` macro.
 // By deriving `Serialize`, we allow this struct to be converted to JSON.
 #[derive(OpenApi, Clone, Serialize)]
 #[openapi(
     paths(
         upload_schema,
+        delete_schema,
         create_item,
+        create_items_batch,
+        list_items,
         get_item,
         update_item,
         delete_item,
+        get_blob,
+        item_history,
+        changelog,
+        revert_item,
+        register_user,
+        login_user,
     ),
     components(
-        schemas(SchemaUpload)
+        schemas(SchemaUpload, auth::Credentials)
     ),
+    modifiers(&SecurityAddon),
     info(
         title = "Dynamic CRUD API",
         version = "1.0.0",
@@ -46,12 +120,37 @@ const DEFAULT_PORT: u16 = 7777;
 )]
 struct ApiDoc;
 
+// Registers the `bearerAuth` HTTP security scheme on the generated OpenAPI
+// document so Swagger UI shows the lock icon and an authorize button on
+// protected paths.
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Components::new);
+        components.add_security_scheme(
+            "bearerAuth",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+    }
+}
+
+/// A `bearerAuth` security requirement, attached to every mutating path so
+/// Swagger UI marks it as needing the authorize button.
+fn bearer_auth_requirement() -> SecurityRequirement {
+    SecurityRequirement::new("bearerAuth", Vec::<String>::new())
+}
+
 
 // Define a thread-safe, in-memory store for schemas and data.
 struct AppState {
-    schemas: Mutex<HashMap<String, Value>>,
-    data: Mutex<HashMap<String, HashMap<String, Value>>>,
-    apidocs: Mutex<utoipa::openapi::OpenApi>
+    schemas: Mutex<HashMap<String, Arc<schema_cache::CompiledSchema>>>,
+    store: storage::Store,
+    blobs: blobs::BlobStore,
+    apidocs: Mutex<utoipa::openapi::OpenApi>,
+    // Username of whoever uploaded each model's schema, used to enforce
+    // per-model write access alongside the `admin` role.
+    schema_owners: Mutex<HashMap<String, String>>,
 }
 
 // A helper struct for request bodies. The `ToSchema` derive generates
@@ -69,6 +168,141 @@ async fn index() -> impl Responder {
 }
 
 
+// Translate a user-uploaded JSON Schema fragment into the `utoipa` schema
+// representation so it can be merged into the live OpenAPI document. Only
+// a subset of JSON Schema is translated: objects (with properties/required),
+// arrays, the scalar types, and `$ref` passthrough for schemas that
+// reference another model; other keywords are left out of the document.
+fn json_schema_to_openapi(value: &Value) -> RefOr<Schema> {
+    if let Some(reference) = value.get("$ref").and_then(Value::as_str) {
+        return RefOr::Ref(Ref::new(reference));
+    }
+
+    let schema_type = value.get("type").and_then(Value::as_str).unwrap_or("object");
+
+    match schema_type {
+        "object" => {
+            let mut builder = ObjectBuilder::new();
+
+            if let Some(properties) = value.get("properties").and_then(Value::as_object) {
+                for (prop_name, prop_schema) in properties {
+                    builder = builder.property(prop_name, json_schema_to_openapi(prop_schema));
+                }
+            }
+
+            if let Some(required) = value.get("required").and_then(Value::as_array) {
+                for name in required.iter().filter_map(Value::as_str) {
+                    builder = builder.required(name);
+                }
+            }
+
+            RefOr::T(Schema::Object(builder.build()))
+        }
+        "array" => {
+            let items = value
+                .get("items")
+                .map(json_schema_to_openapi)
+                .unwrap_or_else(|| RefOr::T(Schema::Object(ObjectBuilder::new().build())));
+
+            RefOr::T(Schema::Array(ArrayBuilder::new().items(items).build()))
+        }
+        "string" => RefOr::T(Schema::Object(ObjectBuilder::new().schema_type(SchemaType::String).build())),
+        "number" => RefOr::T(Schema::Object(ObjectBuilder::new().schema_type(SchemaType::Number).build())),
+        "integer" => RefOr::T(Schema::Object(ObjectBuilder::new().schema_type(SchemaType::Integer).build())),
+        "boolean" => RefOr::T(Schema::Object(ObjectBuilder::new().schema_type(SchemaType::Boolean).build())),
+        _ => RefOr::T(Schema::Object(ObjectBuilder::new().build())),
+    }
+}
+
+// Build the CRUD `PathItem`s for a dynamically uploaded model and merge
+// them, along with its translated schema, into the live OpenAPI document
+// so Swagger UI reflects whatever models are currently uploaded.
+fn register_model_in_openapi(apidoc: &mut openapi::OpenApi, name: &str, schema: RefOr<Schema>) {
+    apidoc
+        .components
+        .get_or_insert_with(Components::new)
+        .schemas
+        .insert(name.to_string(), schema);
+
+    let item_ref = RefOr::Ref(Ref::from_schema_name(name));
+
+    let created_response = ResponseBuilder::new()
+        .description("Item created successfully")
+        .content("application/json", ContentBuilder::new().schema(item_ref.clone()).build())
+        .build();
+    let found_response = ResponseBuilder::new()
+        .description("Item found")
+        .content("application/json", ContentBuilder::new().schema(item_ref.clone()).build())
+        .build();
+    let updated_response = ResponseBuilder::new()
+        .description("Item updated successfully")
+        .content("application/json", ContentBuilder::new().schema(item_ref.clone()).build())
+        .build();
+    let deleted_response = ResponseBuilder::new().description("Item deleted successfully").build();
+
+    let body = RequestBodyBuilder::new()
+        .content("application/json", ContentBuilder::new().schema(item_ref).build())
+        .build();
+
+    let create_op = OperationBuilder::new()
+        .request_body(Some(body.clone()))
+        .response("201", created_response)
+        .security(vec![bearer_auth_requirement()])
+        .build();
+
+    let list_op = OperationBuilder::new()
+        .response(
+            "200",
+            ResponseBuilder::new().description("Items matching the query").build(),
+        )
+        .build();
+
+    let id_param = Parameter::new("id")
+        .parameter_in(ParameterIn::Path)
+        .required(Required::True);
+
+    let get_op = OperationBuilder::new()
+        .parameter(id_param.clone())
+        .response("200", found_response)
+        .response("404", ResponseBuilder::new().description("Item not found").build())
+        .build();
+
+    let update_op = OperationBuilder::new()
+        .parameter(id_param.clone())
+        .request_body(Some(body))
+        .response("200", updated_response)
+        .response("404", ResponseBuilder::new().description("Item not found").build())
+        .security(vec![bearer_auth_requirement()])
+        .build();
+
+    let delete_op = OperationBuilder::new()
+        .parameter(id_param)
+        .response("200", deleted_response)
+        .response("404", ResponseBuilder::new().description("Item not found").build())
+        .security(vec![bearer_auth_requirement()])
+        .build();
+
+    let mut collection = PathItem::new(PathItemType::Post, create_op);
+    collection.get = Some(list_op);
+    apidoc.paths.paths.insert(format!("/api/{name}"), collection);
+
+    let mut by_id = PathItem::new(PathItemType::Get, get_op);
+    by_id.put = Some(update_op);
+    by_id.delete = Some(delete_op);
+    apidoc.paths.paths.insert(format!("/api/{name}/{{id}}"), by_id);
+}
+
+// Remove a model's schema and generated paths from the live OpenAPI
+// document, mirroring `register_model_in_openapi` for the delete/re-upload
+// case.
+fn unregister_model_from_openapi(apidoc: &mut openapi::OpenApi, name: &str) {
+    if let Some(components) = apidoc.components.as_mut() {
+        components.schemas.remove(name);
+    }
+    apidoc.paths.paths.remove(&format!("/api/{name}"));
+    apidoc.paths.paths.remove(&format!("/api/{name}/{{id}}"));
+}
+
 // Handler for uploading a new JSON Schema.
 // The `#[utoipa::path]` attribute documents this endpoint.
 #[utoipa::path(
@@ -90,16 +324,47 @@ async fn index() -> impl Responder {
     ),
     responses(
         (status = 200, description = "Schema uploaded successfully")
-    )
+    ),
+    security(("bearerAuth" = []))
 )]
 async fn upload_schema(
     req_body: web::Json<SchemaUpload>,
     data: web::Data<AppState>,
+    user: auth::AuthenticatedUser,
 ) -> impl Responder {
+    // Compile once at upload time so `create_item`/`update_item` just
+    // borrow the cached validator instead of recompiling per request, and
+    // reject a malformed schema here rather than on the first write.
+    let compiled = match schema_cache::CompiledSchema::compile(req_body.schema.clone()) {
+        Ok(compiled) => compiled,
+        Err(message) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Invalid JSON Schema: {}", message)
+            }))
+        }
+    };
+
+    let mut owners = data.schema_owners.lock().unwrap();
+
+    // Re-uploading an existing model's schema is an ownership-gated
+    // mutation, same as `delete_schema`: otherwise any authenticated user
+    // could overwrite someone else's schema and seize ownership of it.
+    if !auth::can_mutate(owners.get(&req_body.name).map(String::as_str), &user.0) {
+        return HttpResponse::Forbidden().body(format!("Not authorized to modify model '{}'", req_body.name));
+    }
+
     let mut schemas = data.schemas.lock().unwrap();
 
     // Use the name provided in the request body to store the schema.
-    schemas.insert(req_body.name.clone(), req_body.schema.clone());
+    schemas.insert(req_body.name.clone(), Arc::new(compiled));
+    owners.insert(req_body.name.clone(), user.0.sub.clone());
+
+    // Keep the live OpenAPI document in sync so Swagger UI documents the
+    // paths and request/response bodies for this model right away.
+    let openapi_schema = json_schema_to_openapi(&req_body.schema);
+    let mut apidocs = data.apidocs.lock().unwrap();
+    register_model_in_openapi(&mut apidocs, &req_body.name, openapi_schema);
 
     HttpResponse::Ok().json(json!({
         "status": "success",
@@ -107,24 +372,51 @@ async fn upload_schema(
     }))
 }
 
-// Helper function to validate JSON data against a schema.
-// This logic remains the same.
-fn validate_data(
-    schema: &Value,
-    instance: &Value,
-) -> Result<(), Vec<String>> {
-    let compiled_schema = JSONSchema::options()
-        .compile(schema)
-        .map_err(|e| vec![e.to_string()])?;
+// Handler for removing a previously uploaded JSON Schema, which also drops
+// the model's generated paths from the live OpenAPI document. Existing
+// data for the model is left untouched so re-uploading the schema later
+// brings the paths back for it.
+#[utoipa::path(
+    delete,
+    path = "/api/schema/{name}",
+    responses(
+        (status = 200, description = "Schema removed successfully"),
+        (status = 404, description = "No schema found for that name")
+    ),
+    params(
+        ("name", description = "The name of the data model")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn delete_schema(
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+    user: auth::AuthenticatedUser,
+) -> impl Responder {
+    let name = path.into_inner();
+    let mut owners = data.schema_owners.lock().unwrap();
+
+    if !auth::can_mutate(owners.get(&name).map(String::as_str), &user.0) {
+        return HttpResponse::Forbidden().body(format!("Not authorized to modify model '{}'", name));
+    }
 
-    if let Err(errors) = compiled_schema.validate(instance) {
-        let error_messages: Vec<String> = errors.into_iter().map(|e| e.to_string()).collect();
-        return Err(error_messages);
+    let mut schemas = data.schemas.lock().unwrap();
+
+    if schemas.remove(&name).is_none() {
+        return HttpResponse::NotFound().body(format!("No schema found for model '{}'", name));
     }
+    owners.remove(&name);
+
+    let mut apidocs = data.apidocs.lock().unwrap();
+    unregister_model_from_openapi(&mut apidocs, &name);
 
-    Ok(())
+    HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": format!("Schema for '{}' removed successfully.", name)
+    }))
 }
 
+
 // Handler for creating a new item.
 #[utoipa::path(
     post,
@@ -142,22 +434,31 @@ fn validate_data(
     ),
     params(
         ("model_name", description = "The name of the data model")
-    )
+    ),
+    security(("bearerAuth" = []))
 )]
 async fn create_item(
     path: web::Path<String>,
     item: web::Json<Value>,
     data: web::Data<AppState>,
+    user: auth::AuthenticatedUser,
 ) -> impl Responder {
     let model_name = path.into_inner();
     let schemas = data.schemas.lock().unwrap();
 
     let schema = match schemas.get(&model_name) {
-        Some(s) => s,
+        Some(s) => s.clone(),
         None => return HttpResponse::BadRequest().body(format!("No schema found for model '{}'", model_name)),
     };
+    drop(schemas);
 
-    if let Err(errors) = validate_data(schema, &item) {
+    let owners = data.schema_owners.lock().unwrap();
+    if !auth::can_mutate(owners.get(&model_name).map(String::as_str), &user.0) {
+        return HttpResponse::Forbidden().body(format!("Not authorized to modify model '{}'", model_name));
+    }
+    drop(owners);
+
+    if let Err(errors) = schema.validate(&item) {
         return HttpResponse::BadRequest().json(json!({
             "status": "error",
             "message": "Validation failed",
@@ -165,17 +466,255 @@ async fn create_item(
         }));
     }
 
-    let mut item_data = data.data.lock().unwrap();
-    let model_data = item_data.entry(model_name.clone()).or_insert_with(HashMap::new);
+    match data.store.create(&model_name, &user.0.sub, item.into_inner()) {
+        Ok((id, value)) => HttpResponse::Created().json(json!({
+            "status": "success",
+            "id": id,
+            "data": value
+        })),
+        Err(message) => HttpResponse::InternalServerError().body(message),
+    }
+}
+
+// Read a `multipart/form-data` body into a JSON object, uploading any part
+// whose field name is one of `blob_fields` to the blob store and storing
+// just its content hash, and reading every other part as a JSON value,
+// falling back to a plain string when it doesn't parse.
+async fn read_multipart_item(
+    mut payload: Multipart,
+    blob_fields: &[String],
+    blob_store: &blobs::BlobStore,
+) -> Result<Value, String> {
+    let mut item = serde_json::Map::new();
 
-    let id = format!("{}", uuid::Uuid::new_v4());
+    while let Some(mut field) = payload.try_next().await.map_err(|e| e.to_string())? {
+        let name = field
+            .content_disposition()
+            .and_then(|cd| cd.get_name())
+            .ok_or_else(|| "multipart field missing a name".to_string())?
+            .to_string();
+        let mime = field.content_type().map(|m| m.to_string());
 
-    model_data.insert(id.clone(), item.into_inner());
+        let mut bytes = BytesMut::new();
+        while let Some(chunk) = field.try_next().await.map_err(|e| e.to_string())? {
+            bytes.extend_from_slice(&chunk);
+        }
 
-    HttpResponse::Created().json(json!({
-        "status": "success",
-        "id": id,
-        "data": model_data.get(&id)
+        if blob_fields.contains(&name) {
+            let hash = blob_store.put(&bytes, mime.as_deref()).map_err(|e| e.to_string())?;
+            item.insert(name, json!(hash));
+        } else {
+            // Form fields arrive as raw bytes with no type info, so a
+            // non-blob field for a typed property (e.g. `completed:
+            // boolean`, `priority: integer`) would fail schema validation
+            // if stored as a plain string. Try to parse it as JSON first
+            // (covers booleans, numbers, and quoted/JSON-encoded strings)
+            // and fall back to a plain string for anything else. A nested
+            // object or array has to arrive as one JSON-encoded part; it
+            // can't be split across several parts, and blob fields are
+            // only recognised among the schema's top-level properties.
+            let text = String::from_utf8_lossy(&bytes).into_owned();
+            let value = serde_json::from_str(&text).unwrap_or(Value::String(text));
+            item.insert(name, value);
+        }
+    }
+
+    Ok(Value::Object(item))
+}
+
+// Handler for creating a new item from a `multipart/form-data` body, used
+// when the model's schema has one or more `format: "binary"` (or
+// `x-blob`) properties. Same route and response shape as `create_item`;
+// not separately documented in the OpenAPI output to avoid registering
+// the same path/method twice.
+async fn create_item_multipart(
+    path: web::Path<String>,
+    payload: Multipart,
+    data: web::Data<AppState>,
+    user: auth::AuthenticatedUser,
+) -> impl Responder {
+    let model_name = path.into_inner();
+    let schemas = data.schemas.lock().unwrap();
+
+    let schema = match schemas.get(&model_name) {
+        Some(s) => s.clone(),
+        None => return HttpResponse::BadRequest().body(format!("No schema found for model '{}'", model_name)),
+    };
+    drop(schemas);
+
+    let owners = data.schema_owners.lock().unwrap();
+    if !auth::can_mutate(owners.get(&model_name).map(String::as_str), &user.0) {
+        return HttpResponse::Forbidden().body(format!("Not authorized to modify model '{}'", model_name));
+    }
+    drop(owners);
+
+    let blob_fields = blobs::blob_fields(schema.raw());
+    let item = match read_multipart_item(payload, &blob_fields, &data.blobs).await {
+        Ok(item) => item,
+        Err(message) => return HttpResponse::BadRequest().json(json!({ "status": "error", "message": message })),
+    };
+
+    if let Err(errors) = schema.validate(&item) {
+        return HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": "Validation failed",
+            "errors": errors
+        }));
+    }
+
+    match data.store.create(&model_name, &user.0.sub, item) {
+        Ok((id, value)) => HttpResponse::Created().json(json!({
+            "status": "success",
+            "id": id,
+            "data": value
+        })),
+        Err(message) => HttpResponse::InternalServerError().body(message),
+    }
+}
+
+// Handler for creating many items in one call. Every element is validated
+// against the schema before anything is inserted: if any element fails,
+// the whole batch is rejected with per-index error details and nothing is
+// written, avoiding the N-round-trip cost of seeding many records through
+// `create_item` one at a time.
+#[utoipa::path(
+    post,
+    path = "/api/{model_name}/batch",
+    request_body(
+        content_type = "application/json",
+        example = json!([
+            { "title": "Learn Rust", "completed": false },
+            { "title": "Master Rust", "completed": false }
+        ])
+    ),
+    responses(
+        (status = 201, description = "All items created successfully"),
+        (status = 400, description = "No schema found, or one or more items failed validation")
+    ),
+    params(
+        ("model_name", description = "The name of the data model")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn create_items_batch(
+    path: web::Path<String>,
+    items: web::Json<Vec<Value>>,
+    data: web::Data<AppState>,
+    user: auth::AuthenticatedUser,
+) -> impl Responder {
+    let model_name = path.into_inner();
+    let schemas = data.schemas.lock().unwrap();
+
+    let schema = match schemas.get(&model_name) {
+        Some(s) => s.clone(),
+        None => return HttpResponse::BadRequest().body(format!("No schema found for model '{}'", model_name)),
+    };
+    drop(schemas);
+
+    let owners = data.schema_owners.lock().unwrap();
+    if !auth::can_mutate(owners.get(&model_name).map(String::as_str), &user.0) {
+        return HttpResponse::Forbidden().body(format!("Not authorized to modify model '{}'", model_name));
+    }
+    drop(owners);
+
+    let errors_by_index: Vec<Value> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| schema.validate(item).err().map(|errors| json!({ "index": index, "errors": errors })))
+        .collect();
+
+    if !errors_by_index.is_empty() {
+        return HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": "Validation failed",
+            "errors": errors_by_index
+        }));
+    }
+
+    match data.store.create_batch(&model_name, &user.0.sub, items.into_inner()) {
+        Ok(created) => {
+            let created: Vec<Value> = created
+                .into_iter()
+                .enumerate()
+                .map(|(index, (id, _))| json!({ "index": index, "id": id }))
+                .collect();
+            let count = created.len();
+
+            HttpResponse::Created().json(json!({ "created": created, "count": count }))
+        }
+        Err(message) => HttpResponse::InternalServerError().body(message),
+    }
+}
+
+// Query params accepted by `list_items` to control pagination; filter
+// expressions live directly in the raw query string and are parsed by the
+// `query` module instead, since their keys are arbitrary field names.
+#[derive(Deserialize)]
+struct Pagination {
+    limit: Option<usize>,
+    offset: Option<usize>,
+}
+
+// Handler for listing items of a model, optionally filtered and paginated.
+#[utoipa::path(
+    get,
+    path = "/api/{model_name}",
+    responses(
+        (status = 200, description = "Items matching the query"),
+        (status = 400, description = "No schema found, or an invalid filter expression")
+    ),
+    params(
+        ("model_name", description = "The name of the data model"),
+        ("limit", description = "Maximum number of items to return"),
+        ("offset", description = "Number of items to skip before collecting `limit` of them")
+    )
+)]
+async fn list_items(
+    path: web::Path<String>,
+    req: HttpRequest,
+    pagination: web::Query<Pagination>,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    let model_name = path.into_inner();
+
+    if !data.schemas.lock().unwrap().contains_key(&model_name) {
+        return HttpResponse::BadRequest().body(format!("No schema found for model '{}'", model_name));
+    }
+
+    // A model gains a key in the store's state map only once its first item
+    // is created, so a freshly-schema'd model with no items yet is a valid
+    // empty listing rather than an unknown model.
+    let model_data = data.store.list(&model_name).unwrap_or_default();
+
+    let filters = match query::parse_filters(req.query_string()) {
+        Ok(filters) => filters,
+        Err(message) => {
+            return HttpResponse::BadRequest().json(json!({ "status": "error", "message": message }))
+        }
+    };
+
+    // Sort by id so pagination is stable across requests; a `HashMap`'s
+    // own iteration order is not.
+    let mut matching: Vec<(String, Value)> =
+        model_data.into_iter().filter(|(_, item)| query::matches(item, &filters)).collect();
+    matching.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let total = matching.len();
+    let offset = pagination.offset.unwrap_or(0);
+    let limit = pagination.limit.unwrap_or(total);
+
+    let items: Vec<Value> = matching
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|(id, item)| json!({ "id": id, "data": item }))
+        .collect();
+
+    HttpResponse::Ok().json(json!({
+        "items": items,
+        "total": total,
+        "limit": limit,
+        "offset": offset
     }))
 }
 
@@ -197,18 +736,15 @@ async fn get_item(
     data: web::Data<AppState>,
 ) -> impl Responder {
     let (model_name, item_id) = path.into_inner();
-    let item_data = data.data.lock().unwrap();
-
-    if let Some(model_data) = item_data.get(&model_name) {
-        if let Some(item) = model_data.get(&item_id) {
-            return HttpResponse::Ok().json(json!({
-                "status": "success",
-                "data": item
-            }));
-        }
-    }
 
-    HttpResponse::NotFound().body(format!("Item with ID '{}' not found in model '{}'", item_id, model_name))
+    match data.store.get(&model_name, &item_id) {
+        Some(item) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": item
+        })),
+        None => HttpResponse::NotFound()
+            .body(format!("Item with ID '{}' not found in model '{}'", item_id, model_name)),
+    }
 }
 
 // Handler for updating an item.
@@ -230,22 +766,31 @@ async fn get_item(
     params(
         ("model_name", description = "The name of the data model"),
         ("id", description = "The unique ID of the item")
-    )
+    ),
+    security(("bearerAuth" = []))
 )]
 async fn update_item(
     path: web::Path<(String, String)>,
     item: web::Json<Value>,
     data: web::Data<AppState>,
+    user: auth::AuthenticatedUser,
 ) -> impl Responder {
     let (model_name, item_id) = path.into_inner();
     let schemas = data.schemas.lock().unwrap();
 
     let schema = match schemas.get(&model_name) {
-        Some(s) => s,
+        Some(s) => s.clone(),
         None => return HttpResponse::BadRequest().body(format!("No schema found for model '{}'", model_name)),
     };
+    drop(schemas);
+
+    let owners = data.schema_owners.lock().unwrap();
+    if !auth::can_mutate(owners.get(&model_name).map(String::as_str), &user.0) {
+        return HttpResponse::Forbidden().body(format!("Not authorized to modify model '{}'", model_name));
+    }
+    drop(owners);
 
-    if let Err(errors) = validate_data(schema, &item) {
+    if let Err(errors) = schema.validate(&item) {
         return HttpResponse::BadRequest().json(json!({
             "status": "error",
             "message": "Validation failed",
@@ -253,19 +798,67 @@ async fn update_item(
         }));
     }
 
-    let mut item_data = data.data.lock().unwrap();
-    if let Some(model_data) = item_data.get_mut(&model_name) {
-        if let Some(existing_item) = model_data.get_mut(&item_id) {
-            *existing_item = item.into_inner();
-            return HttpResponse::Ok().json(json!({
-                "status": "success",
-                "message": format!("Item with ID '{}' updated successfully", item_id),
-                "data": existing_item
-            }));
-        }
+    match data.store.update(&model_name, &item_id, &user.0.sub, item.into_inner()) {
+        Ok(Some(value)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": format!("Item with ID '{}' updated successfully", item_id),
+            "data": value
+        })),
+        Ok(None) => HttpResponse::NotFound()
+            .body(format!("Item with ID '{}' not found in model '{}'", item_id, model_name)),
+        Err(message) => HttpResponse::InternalServerError().body(message),
     }
+}
 
-    HttpResponse::NotFound().body(format!("Item with ID '{}' not found in model '{}'", item_id, model_name))
+// Handler for updating an item from a `multipart/form-data` body, the
+// update-side counterpart of `create_item_multipart`. Same route and
+// response shape as `update_item`; not separately documented for the same
+// reason.
+async fn update_item_multipart(
+    path: web::Path<(String, String)>,
+    payload: Multipart,
+    data: web::Data<AppState>,
+    user: auth::AuthenticatedUser,
+) -> impl Responder {
+    let (model_name, item_id) = path.into_inner();
+    let schemas = data.schemas.lock().unwrap();
+
+    let schema = match schemas.get(&model_name) {
+        Some(s) => s.clone(),
+        None => return HttpResponse::BadRequest().body(format!("No schema found for model '{}'", model_name)),
+    };
+    drop(schemas);
+
+    let owners = data.schema_owners.lock().unwrap();
+    if !auth::can_mutate(owners.get(&model_name).map(String::as_str), &user.0) {
+        return HttpResponse::Forbidden().body(format!("Not authorized to modify model '{}'", model_name));
+    }
+    drop(owners);
+
+    let blob_fields = blobs::blob_fields(schema.raw());
+    let item = match read_multipart_item(payload, &blob_fields, &data.blobs).await {
+        Ok(item) => item,
+        Err(message) => return HttpResponse::BadRequest().json(json!({ "status": "error", "message": message })),
+    };
+
+    if let Err(errors) = schema.validate(&item) {
+        return HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": "Validation failed",
+            "errors": errors
+        }));
+    }
+
+    match data.store.update(&model_name, &item_id, &user.0.sub, item) {
+        Ok(Some(value)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": format!("Item with ID '{}' updated successfully", item_id),
+            "data": value
+        })),
+        Ok(None) => HttpResponse::NotFound()
+            .body(format!("Item with ID '{}' not found in model '{}'", item_id, model_name)),
+        Err(message) => HttpResponse::InternalServerError().body(message),
+    }
 }
 
 // Handler for deleting an item.
@@ -279,28 +872,185 @@ async fn update_item(
     params(
         ("model_name", description = "The name of the data model"),
         ("id", description = "The unique ID of the item")
-    )
+    ),
+    security(("bearerAuth" = []))
 )]
 async fn delete_item(
     path: web::Path<(String, String)>,
     data: web::Data<AppState>,
+    user: auth::AuthenticatedUser,
 ) -> impl Responder {
     let (model_name, item_id) = path.into_inner();
-    let mut item_data = data.data.lock().unwrap();
-
-    if let Some(model_data) = item_data.get_mut(&model_name) {
-        if model_data.remove(&item_id).is_some() {
-            return HttpResponse::Ok().json(json!({
-                "status": "success",
-                "message": format!("Item with ID '{}' deleted successfully", item_id)
-            }));
-        }
+
+    let owners = data.schema_owners.lock().unwrap();
+    if !auth::can_mutate(owners.get(&model_name).map(String::as_str), &user.0) {
+        return HttpResponse::Forbidden().body(format!("Not authorized to modify model '{}'", model_name));
     }
+    drop(owners);
 
-    HttpResponse::NotFound().body(format!("Item with ID '{}' not found in model '{}'", item_id, model_name))
+    match data.store.delete(&model_name, &item_id, &user.0.sub) {
+        Ok(true) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": format!("Item with ID '{}' deleted successfully", item_id)
+        })),
+        Ok(false) => HttpResponse::NotFound()
+            .body(format!("Item with ID '{}' not found in model '{}'", item_id, model_name)),
+        Err(message) => HttpResponse::InternalServerError().body(message),
+    }
 }
 
 
+// Handler serving a previously uploaded blob's raw bytes back by its
+// content hash, with its recorded MIME type and long-lived caching headers
+// (the hash already changes if the content does, so the response is safe
+// to cache forever).
+#[utoipa::path(
+    get,
+    path = "/api/blob/{hash}",
+    responses(
+        (status = 200, description = "Blob bytes"),
+        (status = 404, description = "No blob found for that hash")
+    ),
+    params(
+        ("hash", description = "The base58-encoded SHA-256 hash addressing the blob")
+    )
+)]
+async fn get_blob(path: web::Path<String>, data: web::Data<AppState>) -> impl Responder {
+    let hash = path.into_inner();
+
+    match data.blobs.get(&hash) {
+        Ok(Some((bytes, mime))) => HttpResponse::Ok()
+            .content_type(mime.unwrap_or_else(|| "application/octet-stream".to_string()))
+            .insert_header((http::header::CACHE_CONTROL, "public, max-age=31536000, immutable"))
+            .body(bytes),
+        Ok(None) => HttpResponse::NotFound().body(format!("No blob found for hash '{}'", hash)),
+        Err(message) => HttpResponse::InternalServerError().body(message),
+    }
+}
+
+// Handler returning the ordered edit history for a single item.
+#[utoipa::path(
+    get,
+    path = "/api/{model_name}/{id}/history",
+    responses(
+        (status = 200, description = "Ordered edit history for the item")
+    ),
+    params(
+        ("model_name", description = "The name of the data model"),
+        ("id", description = "The unique ID of the item")
+    )
+)]
+async fn item_history(path: web::Path<(String, String)>, data: web::Data<AppState>) -> impl Responder {
+    let (model_name, item_id) = path.into_inner();
+    HttpResponse::Ok().json(json!({ "history": data.store.history(&model_name, &item_id) }))
+}
+
+// Handler returning the full changelog across every model, oldest first.
+#[utoipa::path(
+    get,
+    path = "/api/changelog",
+    responses(
+        (status = 200, description = "Every edit recorded across all models")
+    )
+)]
+async fn changelog(data: web::Data<AppState>) -> impl Responder {
+    HttpResponse::Ok().json(json!({ "changelog": data.store.changelog() }))
+}
+
+// Handler reverting an item to the value it held right after `edit_id`,
+// recorded as a new compensating edit rather than mutating history.
+#[utoipa::path(
+    post,
+    path = "/api/{model_name}/{id}/revert/{edit_id}",
+    responses(
+        (status = 200, description = "Item reverted successfully"),
+        (status = 404, description = "No such edit for that item")
+    ),
+    params(
+        ("model_name", description = "The name of the data model"),
+        ("id", description = "The unique ID of the item"),
+        ("edit_id", description = "The edit to revert to")
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn revert_item(
+    path: web::Path<(String, String, String)>,
+    data: web::Data<AppState>,
+    user: auth::AuthenticatedUser,
+) -> impl Responder {
+    let (model_name, item_id, edit_id) = path.into_inner();
+
+    let owners = data.schema_owners.lock().unwrap();
+    if !auth::can_mutate(owners.get(&model_name).map(String::as_str), &user.0) {
+        return HttpResponse::Forbidden().body(format!("Not authorized to modify model '{}'", model_name));
+    }
+    drop(owners);
+
+    match data.store.revert(&model_name, &item_id, &edit_id, &user.0.sub) {
+        Ok(value) => HttpResponse::Ok().json(json!({ "status": "success", "data": value })),
+        Err(message) => HttpResponse::NotFound().json(json!({ "status": "error", "message": message })),
+    }
+}
+
+// Handler for registering a new user. Passwords are hashed with argon2
+// before being stored; nothing plaintext ever touches `AuthState`.
+#[utoipa::path(
+    post,
+    path = "/api/auth/register",
+    request_body(
+        content_type = "application/json",
+        example = json!({ "username": "alice", "password": "hunter2" })
+    ),
+    responses(
+        (status = 200, description = "User registered successfully"),
+        (status = 400, description = "Username already taken")
+    )
+)]
+async fn register_user(
+    req_body: web::Json<auth::Credentials>,
+    auth_state: web::Data<auth::AuthState>,
+) -> impl Responder {
+    let mut users = auth_state.users.lock().unwrap();
+
+    match auth::register(&mut users, &req_body) {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": format!("User '{}' registered successfully.", req_body.username)
+        })),
+        Err(message) => HttpResponse::BadRequest().json(json!({ "status": "error", "message": message })),
+    }
+}
+
+// Handler for logging in, returning a signed JWT on success.
+#[utoipa::path(
+    post,
+    path = "/api/auth/login",
+    request_body(
+        content_type = "application/json",
+        example = json!({ "username": "alice", "password": "hunter2" })
+    ),
+    responses(
+        (status = 200, description = "Login succeeded, returns a bearer token"),
+        (status = 401, description = "Invalid username or password")
+    )
+)]
+async fn login_user(
+    req_body: web::Json<auth::Credentials>,
+    auth_state: web::Data<auth::AuthState>,
+) -> impl Responder {
+    let users = auth_state.users.lock().unwrap();
+
+    let user = match users.get(&req_body.username) {
+        Some(user) if auth::verify_password(user, &req_body.password) => user,
+        _ => return HttpResponse::Unauthorized().body("Invalid username or password"),
+    };
+
+    match auth::issue_token(&auth_state.jwt_secret, &req_body.username, &user.roles) {
+        Ok(token) => HttpResponse::Ok().json(json!({ "status": "success", "token": token })),
+        Err(message) => HttpResponse::InternalServerError().body(message),
+    }
+}
+
 // Handler to serve the OpenAPI JSON spec.
 async fn serve_openapi_spec(app_state: web::Data<AppState>) -> impl Responder {
     // Return the OpenAPI spec as JSON. The `Serialize` trait on `ApiDoc`
@@ -309,18 +1059,41 @@ async fn serve_openapi_spec(app_state: web::Data<AppState>) -> impl Responder {
 }
 
 
+// A guard routing `multipart/form-data` bodies to the `*_multipart`
+// handlers, leaving plain JSON bodies on the default route.
+fn multipart_guard() -> impl guard::Guard {
+    guard::fn_guard(|ctx| {
+        ctx.head()
+            .headers()
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map_or(false, |content_type| content_type.starts_with("multipart/form-data"))
+    })
+}
+
 // Main function to run the web server.
 #[actix_web::main]
 async fn main() -> io::Result<()> {
     // Create the OpenAPI specification from the `ApiDoc` struct.
     let openapi = ApiDoc::openapi();
 
+    let log_path = std::env::var("DYNAMIC_API_LOG_PATH").unwrap_or_else(|_| DEFAULT_LOG_PATH.to_string());
+    let store = storage::Store::open(&log_path)?;
+
+    let blob_dir = std::env::var("DYNAMIC_API_BLOB_DIR").unwrap_or_else(|_| DEFAULT_BLOB_DIR.to_string());
+    let blobs = blobs::BlobStore::open(&blob_dir)?;
+
     let app_state = web::Data::new(AppState {
         schemas: Mutex::new(HashMap::new()),
-        data: Mutex::new(HashMap::new()),
-        apidocs: Mutex::new(openapi.clone())
+        store,
+        blobs,
+        apidocs: Mutex::new(openapi.clone()),
+        schema_owners: Mutex::new(HashMap::new()),
     });
 
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| DEFAULT_JWT_SECRET.to_string());
+    let auth_state = web::Data::new(auth::AuthState::new(jwt_secret));
+
     println!("Service is running at http://127.0.0.1:{}", DEFAULT_PORT);
     println!("Swagger UI available at http://127.0.0.1:{}/swagger-ui/", DEFAULT_PORT);
     println!("OpenAPI spec available at http://127.0.0.1:{}/api-docs/openapi.json", DEFAULT_PORT);
@@ -337,6 +1110,7 @@ async fn main() -> io::Result<()> {
             .wrap(cors)
             // Pass the application state to all route handlers.
             .app_data(app_state.clone())
+            .app_data(auth_state.clone())
             // Also pass the OpenAPI spec, which is now serializable.
             //.app_data(web::Data::new(openapi.clone()))
             // Serve the static files from a local directory.
@@ -347,13 +1121,32 @@ async fn main() -> io::Result<()> {
             // Define the API routes.
             .service(
                 web::scope("/api")
+                    .service(web::scope("/auth")
+                        .route("/register", web::post().to(register_user))
+                        .route("/login", web::post().to(login_user))
+                    )
                     .service(web::resource("/schema").route(web::post().to(upload_schema)))
-                    .service(web::resource("/{model_name}").route(web::post().to(create_item)))
+                    .service(web::resource("/schema/{name}").route(web::delete().to(delete_schema)))
+                    .service(web::resource("/changelog").route(web::get().to(changelog)))
+                    .service(web::resource("/blob/{hash}").route(web::get().to(get_blob)))
+                    .service(web::resource("/{model_name}/batch").route(web::post().to(create_items_batch)))
+                    .service(
+                        web::resource("/{model_name}")
+                            .route(web::post().guard(multipart_guard()).to(create_item_multipart))
+                            .route(web::post().to(create_item))
+                            .route(web::get().to(list_items)),
+                    )
                     .service(web::resource("/{model_name}/{id}")
                         .route(web::get().to(get_item))
+                        .route(web::put().guard(multipart_guard()).to(update_item_multipart))
                         .route(web::put().to(update_item))
                         .route(web::delete().to(delete_item))
                     )
+                    .service(web::resource("/{model_name}/{id}/history").route(web::get().to(item_history)))
+                    .service(
+                        web::resource("/{model_name}/{id}/revert/{edit_id}")
+                            .route(web::post().to(revert_item)),
+                    )
             )
             .route("/", web::get().to(index))
     })
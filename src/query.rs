@@ -0,0 +1,221 @@
+// A small filter expression grammar for the list/query endpoint.
+//
+// A query string such as `completed=true&title~=rust&priority>=3` is split
+// on `&` into individual `field<op>value` expressions and parsed into
+// `Filter`s, which are then evaluated against each stored item by walking
+// to the named field and comparing by JSON type: numbers numerically,
+// strings by exact match or substring (`~=`), booleans for equality only.
+// The ordering operators (`>`, `>=`, `<`, `<=`) compare numbers only: a
+// non-numeric value after one of them is rejected as a malformed
+// expression, and a string field never matches them.
+//
+// `HttpRequest::query_string()` returns the raw, still percent-encoded
+// query string, so `=`/`!=`/`~=` values are percent-decoded before use.
+// `=`/`!=` don't pre-parse the value into a JSON scalar either: whether
+// `"01234"` means the number 1234 or the string "01234" depends on the
+// stored field's own type, so that decision is made at evaluation time
+// against the actual field, not up front.
+
+use serde_json::Value;
+
+/// Query-string parameters that control pagination rather than filtering,
+/// and are therefore skipped by `parse_filters`.
+const RESERVED_PARAMS: [&str; 2] = ["limit", "offset"];
+
+/// The operators recognised in a filter expression, longest first so that
+/// e.g. `>=` is matched before the plain `>` it contains.
+const OPERATORS: [&str; 7] = ["~=", ">=", "<=", "!=", ">", "<", "="];
+
+/// A single filter comparison parsed out of a query string. `Eq`/`Neq`/
+/// `Contains` keep the percent-decoded value as a plain string; how it
+/// compares against a stored field depends on that field's own JSON type
+/// (see `value_matches`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    Eq { field: String, value: String },
+    Neq { field: String, value: String },
+    Contains { field: String, value: String },
+    Gt { field: String, value: f64 },
+    Gte { field: String, value: f64 },
+    Lt { field: String, value: f64 },
+    Lte { field: String, value: f64 },
+}
+
+/// Parse a raw query string (as returned by `HttpRequest::query_string`)
+/// into a list of filters, skipping pagination params. Returns an error
+/// describing the first malformed expression encountered.
+pub fn parse_filters(query: &str) -> Result<Vec<Filter>, String> {
+    let mut filters = Vec::new();
+
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (field, op, raw_value) =
+            split_operator(pair).ok_or_else(|| format!("invalid filter expression '{pair}'"))?;
+
+        if RESERVED_PARAMS.contains(&field) {
+            continue;
+        }
+
+        filters.push(build_filter(field, op, &percent_decode(raw_value))?);
+    }
+
+    Ok(filters)
+}
+
+/// Find the operator that starts earliest in `pair`, breaking ties between
+/// operators starting at the same index by picking the longest one (so
+/// `>=` wins over `>` when both start at the same position). Scanning
+/// `OPERATORS` independently and returning the first hit would instead
+/// return whichever operator happens to occur first in that list, even if
+/// it starts later in the string than another candidate - e.g. splitting
+/// `a=b>=c` on `>=` because it's listed before `=`, even though `=` is the
+/// leftmost match.
+fn split_operator(pair: &str) -> Option<(&str, &str, &str)> {
+    OPERATORS
+        .iter()
+        .filter_map(|op| pair.find(op).map(|idx| (idx, *op)))
+        .min_by_key(|(idx, op)| (*idx, std::cmp::Reverse(op.len())))
+        .map(|(idx, op)| (&pair[..idx], op, &pair[idx + op.len()..]))
+}
+
+/// Percent-decode a query-string value. Invalid or truncated `%XX`
+/// escapes are passed through unchanged rather than rejected, since a
+/// malformed filter value isn't worth failing the whole request over.
+fn percent_decode(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() && bytes[i + 1..i + 3].iter().all(u8::is_ascii_hexdigit) {
+            if let Ok(byte) = u8::from_str_radix(&raw[i + 1..i + 3], 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn build_filter(field: &str, op: &str, value: &str) -> Result<Filter, String> {
+    let field = field.to_string();
+
+    match op {
+        "=" => Ok(Filter::Eq { field, value: value.to_string() }),
+        "!=" => Ok(Filter::Neq { field, value: value.to_string() }),
+        "~=" => Ok(Filter::Contains { field, value: value.to_string() }),
+        ">" => Ok(Filter::Gt { field, value: parse_number(value)? }),
+        ">=" => Ok(Filter::Gte { field, value: parse_number(value)? }),
+        "<" => Ok(Filter::Lt { field, value: parse_number(value)? }),
+        "<=" => Ok(Filter::Lte { field, value: parse_number(value)? }),
+        _ => Err(format!("unsupported operator '{op}'")),
+    }
+}
+
+fn parse_number(raw: &str) -> Result<f64, String> {
+    raw.parse::<f64>().map_err(|_| format!("expected a number, got '{raw}'"))
+}
+
+/// Does the given item satisfy every filter in `filters`?
+pub fn matches(item: &Value, filters: &[Filter]) -> bool {
+    filters.iter().all(|filter| evaluate(item, filter))
+}
+
+fn evaluate(item: &Value, filter: &Filter) -> bool {
+    match filter {
+        Filter::Eq { field, value } => item.get(field).map_or(false, |actual| value_matches(actual, value)),
+        Filter::Neq { field, value } => item.get(field).map_or(true, |actual| !value_matches(actual, value)),
+        Filter::Contains { field, value } => item
+            .get(field)
+            .and_then(Value::as_str)
+            .map_or(false, |actual| actual.contains(value.as_str())),
+        Filter::Gt { field, value } => compare_number(item, field, |n| n > *value),
+        Filter::Gte { field, value } => compare_number(item, field, |n| n >= *value),
+        Filter::Lt { field, value } => compare_number(item, field, |n| n < *value),
+        Filter::Lte { field, value } => compare_number(item, field, |n| n <= *value),
+    }
+}
+
+/// Does a stored field's value equal the filter's raw (percent-decoded)
+/// string? The comparison is driven by the *stored* value's own type
+/// rather than guessing one from the filter string, so a string field
+/// holding `"01234"` is compared as the string `"01234"`, not coerced to
+/// (and losing the leading zero of) the number 1234.
+fn value_matches(actual: &Value, value: &str) -> bool {
+    match actual {
+        Value::String(s) => s == value,
+        Value::Bool(b) => match value {
+            "true" => *b,
+            "false" => !*b,
+            _ => false,
+        },
+        Value::Number(n) => value.parse::<f64>().ok().map_or(false, |parsed| n.as_f64() == Some(parsed)),
+        Value::Null => value == "null",
+        Value::Array(_) | Value::Object(_) => false,
+    }
+}
+
+fn compare_number(item: &Value, field: &str, cmp: impl Fn(f64) -> bool) -> bool {
+    item.get(field).and_then(Value::as_f64).map_or(false, cmp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn split_operator_picks_the_leftmost_match() {
+        // `>=` occurs inside the value, to the right of the real `=`
+        // separating field from value - the leftmost operator must win.
+        assert_eq!(split_operator("a=b>=c"), Some(("a", "=", "b>=c")));
+        assert_eq!(split_operator("priority>=3"), Some(("priority", ">=", "3")));
+        assert_eq!(split_operator("no-operator-here"), None);
+    }
+
+    #[test]
+    fn parse_filters_skips_pagination_params() {
+        let filters = parse_filters("limit=10&offset=5&completed=true").unwrap();
+        assert_eq!(filters, vec![Filter::Eq { field: "completed".to_string(), value: "true".to_string() }]);
+    }
+
+    #[test]
+    fn parse_filters_percent_decodes_values() {
+        let filters = parse_filters("title~=hello%20world").unwrap();
+        assert_eq!(filters, vec![Filter::Contains { field: "title".to_string(), value: "hello world".to_string() }]);
+    }
+
+    #[test]
+    fn matches_evaluates_every_filter_kind() {
+        let item = json!({ "title": "Learn Rust", "completed": false, "priority": 3 });
+
+        let filters = parse_filters("title~=Rust&completed=false&priority>=3").unwrap();
+        assert!(matches(&item, &filters));
+
+        let filters = parse_filters("completed!=true&priority>2&priority<=3").unwrap();
+        assert!(matches(&item, &filters));
+
+        let filters = parse_filters("priority<3").unwrap();
+        assert!(!matches(&item, &filters));
+    }
+
+    #[test]
+    fn matches_percent_decoded_value_against_a_space_containing_field() {
+        let item = json!({ "title": "hello world" });
+        let filters = parse_filters("title=hello%20world").unwrap();
+        assert!(matches(&item, &filters));
+    }
+
+    #[test]
+    fn eq_compares_a_string_field_as_a_string_even_when_numeric_looking() {
+        let item = json!({ "code": "01234" });
+
+        assert!(matches(&item, &parse_filters("code=01234").unwrap()));
+        // Not equal to the number 1234 - the field is a string and keeps
+        // its leading zero.
+        assert!(!matches(&item, &parse_filters("code=1234").unwrap()));
+    }
+}
@@ -0,0 +1,85 @@
+// A JSON Schema compiled once at upload time and cached for reuse,
+// instead of `jsonschema::JSONSchema::compile` being called again on
+// every create/update (an obvious hot-path cost under load).
+//
+// `JSONSchema` borrows from the `serde_json::Value` it was compiled
+// against, so it can't just sit next to an owned `Value` in a struct
+// without either cloning the schema on every validation or some unsafe
+// bookkeeping. We do the latter: box the schema `Value` so its address is
+// stable across moves, compile against that box, and extend the
+// compiled schema's borrow to `'static`. This is sound only because
+// `compiled` is declared (and therefore dropped) before `value` below, so
+// the borrow never outlives what it points to.
+
+use jsonschema::JSONSchema;
+use serde_json::Value;
+
+/// A compiled validator paired with the raw schema it was compiled from.
+pub struct CompiledSchema {
+    compiled: JSONSchema<'static>,
+    value: Box<Value>,
+}
+
+impl CompiledSchema {
+    /// Compile `schema`, returning an error describing why if it isn't a
+    /// valid JSON Schema document.
+    pub fn compile(schema: Value) -> Result<Self, String> {
+        let value = Box::new(schema);
+
+        let compiled = JSONSchema::options().compile(&value).map_err(|e| e.to_string())?;
+
+        // SAFETY: `value` lives in a `Box` on the heap and is never moved
+        // or mutated for the lifetime of `CompiledSchema`, and the struct
+        // below drops `compiled` before `value`, so this reference never
+        // outlives its referent despite the lifetime we're asserting here.
+        let compiled: JSONSchema<'static> = unsafe { std::mem::transmute(compiled) };
+
+        Ok(Self { compiled, value })
+    }
+
+    /// Validate `instance` against the compiled schema.
+    pub fn validate(&self, instance: &Value) -> Result<(), Vec<String>> {
+        self.compiled.validate(instance).map_err(|errors| errors.map(|e| e.to_string()).collect())
+    }
+
+    /// The raw JSON Schema this validator was compiled from.
+    pub fn raw(&self) -> &Value {
+        &self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // Exercises the compile -> validate -> drop lifecycle the `unsafe`
+    // transmute in `compile` relies on: `compiled` must still be usable
+    // (and must drop cleanly before `value`) after the struct has been
+    // moved around, not just immediately after construction.
+    #[test]
+    fn compiled_schema_validates_and_drops_safely() {
+        let schema = json!({
+            "type": "object",
+            "required": ["title"],
+            "properties": { "title": { "type": "string" } }
+        });
+
+        let compiled = CompiledSchema::compile(schema.clone()).unwrap();
+        assert_eq!(compiled.raw(), &schema);
+
+        assert!(compiled.validate(&json!({ "title": "Learn Rust" })).is_ok());
+        assert!(compiled.validate(&json!({})).is_err());
+
+        // Move it (e.g. into a Vec, as `Arc::new` callers do) before
+        // dropping, to exercise the lifetime-extension across a move.
+        let moved = vec![compiled];
+        assert!(moved[0].validate(&json!({ "title": "Still valid" })).is_ok());
+    }
+
+    #[test]
+    fn invalid_schema_is_rejected_at_compile_time() {
+        let not_a_schema = json!({ "type": "not-a-real-type" });
+        assert!(CompiledSchema::compile(not_a_schema).is_err());
+    }
+}
@@ -0,0 +1,403 @@
+// Durable, append-only storage for item data.
+//
+// Instead of a bare `Mutex<HashMap>` that loses everything on restart,
+// every `create`/`update`/`delete` is recorded as an immutable `Edit`
+// appended to a log file, and the current state is simply the result of
+// replaying that log. This gives auditability (`history`/`changelog`) and
+// crash recovery for free: on startup we just replay whatever is already
+// on disk.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What kind of change an `Edit` represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EditOp {
+    Create,
+    Update,
+    Delete,
+    Revert,
+}
+
+/// A single immutable record in the edit log. `before`/`after` hold the
+/// item's value immediately before and after the edit (`None` meaning "did
+/// not exist"), which is enough to replay history or revert to any point.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Edit {
+    pub edit_id: String,
+    pub model: String,
+    pub item_id: String,
+    pub op: EditOp,
+    pub timestamp: i64,
+    pub author: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
+}
+
+/// The in-memory log and current state, held behind a single lock so that
+/// reading the prior value of an item, appending its edit to disk, and
+/// folding that edit into the log and current state all happen as one
+/// atomic step. Two separate mutexes here would let concurrent writers
+/// interleave between the "read what's there now" and "append" steps,
+/// recording a `before` that was never the actual prior state, and could
+/// let disk and in-memory log order diverge.
+struct Inner {
+    log: Vec<Edit>,
+    state: HashMap<String, HashMap<String, Value>>,
+}
+
+/// The durable, log-backed store: an append-only file on disk plus the
+/// in-memory log and current state derived from replaying it.
+pub struct Store {
+    log_path: PathBuf,
+    inner: Mutex<Inner>,
+}
+
+impl Store {
+    /// Open the store, replaying any edits already recorded at `path` to
+    /// rebuild current state. Creates the file (and its parent directory)
+    /// if it doesn't exist yet.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let log_path = path.into();
+
+        if let Some(parent) = log_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut log = Vec::new();
+        let mut state: HashMap<String, HashMap<String, Value>> = HashMap::new();
+
+        if log_path.exists() {
+            let file = fs::File::open(&log_path)?;
+            for line in BufReader::new(file).lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let edit: Edit = serde_json::from_str(&line)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                apply(&mut state, &edit);
+                log.push(edit);
+            }
+        } else {
+            fs::File::create(&log_path)?;
+        }
+
+        Ok(Self { log_path, inner: Mutex::new(Inner { log, state }) })
+    }
+
+    /// Append `edit` to the on-disk log, then fold it into in-memory state
+    /// and the in-memory log. `inner` must already be locked by the
+    /// caller, covering the read that produced `edit`'s `before` value, so
+    /// the read-then-append sequence is atomic with respect to other
+    /// writers.
+    fn append(&self, inner: &mut Inner, edit: Edit) -> Result<(), String> {
+        self.append_all(inner, vec![edit])
+    }
+
+    /// Append several edits as a single all-or-nothing disk write: either
+    /// every edit lands on disk and is folded into the log/state, or (on
+    /// an I/O or serialization error) none of them are.
+    fn append_all(&self, inner: &mut Inner, edits: Vec<Edit>) -> Result<(), String> {
+        let mut buf = String::new();
+        for edit in &edits {
+            buf.push_str(&serde_json::to_string(edit).map_err(|e| e.to_string())?);
+            buf.push('\n');
+        }
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_path)
+            .and_then(|mut file| file.write_all(buf.as_bytes()))
+            .map_err(|e| e.to_string())?;
+
+        for edit in edits {
+            apply(&mut inner.state, &edit);
+            inner.log.push(edit);
+        }
+
+        Ok(())
+    }
+
+    /// Current value of a single item, if it exists.
+    pub fn get(&self, model: &str, item_id: &str) -> Option<Value> {
+        self.inner.lock().unwrap().state.get(model).and_then(|items| items.get(item_id)).cloned()
+    }
+
+    /// Current items for a model, or `None` if the model has never had an
+    /// item created under it.
+    pub fn list(&self, model: &str) -> Option<HashMap<String, Value>> {
+        self.inner.lock().unwrap().state.get(model).cloned()
+    }
+
+    /// Record a new item, assigning it a fresh id.
+    pub fn create(&self, model: &str, author: &str, value: Value) -> Result<(String, Value), String> {
+        let item_id = uuid::Uuid::new_v4().to_string();
+
+        let edit = Edit {
+            edit_id: uuid::Uuid::new_v4().to_string(),
+            model: model.to_string(),
+            item_id: item_id.clone(),
+            op: EditOp::Create,
+            timestamp: now_unix(),
+            author: author.to_string(),
+            before: None,
+            after: Some(value.clone()),
+        };
+
+        let mut inner = self.inner.lock().unwrap();
+        self.append(&mut inner, edit)?;
+        Ok((item_id, value))
+    }
+
+    /// Create several items in one call, appending all of their edits in a
+    /// single disk write under one lock so the batch is genuinely
+    /// all-or-nothing: if the write fails partway, no edit in the batch is
+    /// folded into the log or current state (unlike mapping `create` over
+    /// `values`, where an error on element N would leave 0..N already
+    /// persisted). Callers are expected to have already validated every
+    /// value, since schema validation itself isn't repeated here.
+    pub fn create_batch(&self, model: &str, author: &str, values: Vec<Value>) -> Result<Vec<(String, Value)>, String> {
+        let created: Vec<(String, Value)> =
+            values.into_iter().map(|value| (uuid::Uuid::new_v4().to_string(), value)).collect();
+
+        let edits = created
+            .iter()
+            .map(|(item_id, value)| Edit {
+                edit_id: uuid::Uuid::new_v4().to_string(),
+                model: model.to_string(),
+                item_id: item_id.clone(),
+                op: EditOp::Create,
+                timestamp: now_unix(),
+                author: author.to_string(),
+                before: None,
+                after: Some(value.clone()),
+            })
+            .collect();
+
+        let mut inner = self.inner.lock().unwrap();
+        self.append_all(&mut inner, edits)?;
+        Ok(created)
+    }
+
+    /// Overwrite an existing item. Returns `Ok(None)` if it doesn't exist.
+    pub fn update(&self, model: &str, item_id: &str, author: &str, value: Value) -> Result<Option<Value>, String> {
+        let mut inner = self.inner.lock().unwrap();
+
+        let before = match inner.state.get(model).and_then(|items| items.get(item_id)).cloned() {
+            Some(before) => before,
+            None => return Ok(None),
+        };
+
+        let edit = Edit {
+            edit_id: uuid::Uuid::new_v4().to_string(),
+            model: model.to_string(),
+            item_id: item_id.to_string(),
+            op: EditOp::Update,
+            timestamp: now_unix(),
+            author: author.to_string(),
+            before: Some(before),
+            after: Some(value.clone()),
+        };
+
+        self.append(&mut inner, edit)?;
+        Ok(Some(value))
+    }
+
+    /// Delete an existing item. Returns `Ok(false)` if it doesn't exist.
+    pub fn delete(&self, model: &str, item_id: &str, author: &str) -> Result<bool, String> {
+        let mut inner = self.inner.lock().unwrap();
+
+        let before = match inner.state.get(model).and_then(|items| items.get(item_id)).cloned() {
+            Some(before) => before,
+            None => return Ok(false),
+        };
+
+        let edit = Edit {
+            edit_id: uuid::Uuid::new_v4().to_string(),
+            model: model.to_string(),
+            item_id: item_id.to_string(),
+            op: EditOp::Delete,
+            timestamp: now_unix(),
+            author: author.to_string(),
+            before: Some(before),
+            after: None,
+        };
+
+        self.append(&mut inner, edit)?;
+        Ok(true)
+    }
+
+    /// The ordered edit history for a single item.
+    pub fn history(&self, model: &str, item_id: &str) -> Vec<Edit> {
+        self.inner
+            .lock()
+            .unwrap()
+            .log
+            .iter()
+            .filter(|edit| edit.model == model && edit.item_id == item_id)
+            .cloned()
+            .collect()
+    }
+
+    /// The full changelog across every model, oldest first.
+    pub fn changelog(&self) -> Vec<Edit> {
+        self.inner.lock().unwrap().log.clone()
+    }
+
+    /// Restore an item to the value it held immediately after `edit_id`,
+    /// writing a new compensating edit rather than mutating history.
+    /// Returns the restored value (`None` if that point in history was
+    /// itself a deletion), or an error if no such edit exists for the item.
+    pub fn revert(&self, model: &str, item_id: &str, edit_id: &str, author: &str) -> Result<Option<Value>, String> {
+        let mut inner = self.inner.lock().unwrap();
+
+        let target = inner
+            .log
+            .iter()
+            .find(|edit| edit.model == model && edit.item_id == item_id && edit.edit_id == edit_id)
+            .cloned()
+            .ok_or_else(|| format!("No edit '{}' found for item '{}' in model '{}'", edit_id, item_id, model))?;
+
+        let before = inner.state.get(model).and_then(|items| items.get(item_id)).cloned();
+        let after = target.after;
+
+        let edit = Edit {
+            edit_id: uuid::Uuid::new_v4().to_string(),
+            model: model.to_string(),
+            item_id: item_id.to_string(),
+            op: EditOp::Revert,
+            timestamp: now_unix(),
+            author: author.to_string(),
+            before,
+            after: after.clone(),
+        };
+
+        self.append(&mut inner, edit)?;
+        Ok(after)
+    }
+}
+
+fn apply(state: &mut HashMap<String, HashMap<String, Value>>, edit: &Edit) {
+    let items = state.entry(edit.model.clone()).or_insert_with(HashMap::new);
+
+    match &edit.after {
+        Some(value) => {
+            items.insert(edit.item_id.clone(), value.clone());
+        }
+        None => {
+            items.remove(&edit.item_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// A fresh log path under the system temp dir, unique per test so
+    /// parallel test runs don't collide.
+    fn temp_log_path() -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("dynamic_api_storage_test_{}_{}.log", std::process::id(), n))
+    }
+
+    #[test]
+    fn reopening_replays_the_log_into_the_same_state() {
+        let path = temp_log_path();
+
+        let (id, _) = {
+            let store = Store::open(&path).unwrap();
+            let (id, value) = store.create("todos", "alice", json!({ "title": "Learn Rust" })).unwrap();
+            store.update("todos", &id, "alice", json!({ "title": "Learn Rust", "completed": true })).unwrap();
+            (id, value)
+        };
+
+        // Reopening from the same path must replay the log back to the
+        // same current state, without re-appending anything.
+        let reopened = Store::open(&path).unwrap();
+        assert_eq!(
+            reopened.get("todos", &id),
+            Some(json!({ "title": "Learn Rust", "completed": true }))
+        );
+        assert_eq!(reopened.history("todos", &id).len(), 2);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn revert_to_a_creation_restores_the_original_value_and_history_is_ordered() {
+        let path = temp_log_path();
+        let store = Store::open(&path).unwrap();
+
+        let (id, original) = store.create("todos", "alice", json!({ "title": "Learn Rust" })).unwrap();
+        store.update("todos", &id, "alice", json!({ "title": "Learn Rust 2" })).unwrap();
+
+        let history = store.history("todos", &id);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].op, EditOp::Create);
+        assert_eq!(history[1].op, EditOp::Update);
+        assert!(history[0].timestamp <= history[1].timestamp);
+
+        let create_edit_id = history[0].edit_id.clone();
+        let restored = store.revert("todos", &id, &create_edit_id, "alice").unwrap();
+        assert_eq!(restored, Some(original));
+        assert_eq!(store.get("todos", &id), Some(json!({ "title": "Learn Rust" })));
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn reverting_to_a_deletion_removes_the_item_again() {
+        let path = temp_log_path();
+        let store = Store::open(&path).unwrap();
+
+        let (id, _) = store.create("todos", "alice", json!({ "title": "Learn Rust" })).unwrap();
+        store.delete("todos", &id, "alice").unwrap();
+
+        let delete_edit_id = store
+            .history("todos", &id)
+            .into_iter()
+            .find(|edit| edit.op == EditOp::Delete)
+            .unwrap()
+            .edit_id;
+
+        // Re-creating under the same id so there's a current value to
+        // revert away from, then reverting to the deletion point.
+        {
+            let mut inner = store.inner.lock().unwrap();
+            store.append(&mut inner, Edit {
+                edit_id: uuid::Uuid::new_v4().to_string(),
+                model: "todos".to_string(),
+                item_id: id.clone(),
+                op: EditOp::Create,
+                timestamp: now_unix(),
+                author: "alice".to_string(),
+                before: None,
+                after: Some(json!({ "title": "Learn Rust again" })),
+            }).unwrap();
+        }
+        assert!(store.get("todos", &id).is_some());
+
+        let restored = store.revert("todos", &id, &delete_edit_id, "alice").unwrap();
+        assert_eq!(restored, None);
+        assert_eq!(store.get("todos", &id), None);
+
+        fs::remove_file(&path).ok();
+    }
+}